@@ -65,7 +65,7 @@ pub(crate) fn esaxx(
 /// assert_eq!(iter.next(), None);
 /// ```
 #[cfg(feature = "cpp")]
-pub fn suffix(string: &str) -> Result<Suffix<i32>, SuffixError> {
+pub fn suffix(string: &str) -> Result<Suffix<char, i32>, SuffixError> {
     let chars: Vec<_> = string.chars().collect();
     let n = chars.len();
     let mut sa = vec![0; n];
@@ -84,7 +84,7 @@ pub fn suffix(string: &str) -> Result<Suffix<i32>, SuffixError> {
         &mut node_num,
     )?;
     Ok(Suffix {
-        chars,
+        symbols: chars,
         suffix_array: sa,
         left_array: l,
         right_array: r,
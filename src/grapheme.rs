@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::esa::esaxx_rs;
+use crate::structs::Suffix;
+use crate::types::SuffixError;
+
+/// Creates the suffix array over the extended grapheme clusters of `string`
+/// (see [`unicode_segmentation`]), rather than over individual `char`s.
+///
+/// Treating each Unicode scalar as a symbol splits "é" written as `e` +
+/// combining accent, ZWJ emoji sequences, and regional-indicator flags
+/// mid-grapheme, so the reported substrings are not valid user-perceived
+/// text. Here each distinct grapheme cluster is interned into a dense `u32`
+/// id first, and `esaxx_rs` runs over the id sequence; the cluster's byte
+/// ranges are kept around so the iterator can hand back real `&str` slices.
+/// ```rust
+/// let suffix = esaxx_rs::suffix_graphemes("abracadabra").unwrap();
+/// let mut iter = suffix.iter();
+/// assert_eq!(iter.next(), Some(("abra", 2)));
+/// assert_eq!(iter.next(), Some(("a", 5)));
+/// ```
+pub fn suffix_graphemes(string: &str) -> Result<GraphemeSuffix, SuffixError> {
+    let mut boundaries: Vec<Range<usize>> = Vec::new();
+    let mut interned: HashMap<&str, u32> = HashMap::new();
+    let mut ids: Vec<u32> = Vec::new();
+    for (start, cluster) in string.grapheme_indices(true) {
+        let next_id = interned.len() as u32;
+        let id = *interned.entry(cluster).or_insert(next_id);
+        ids.push(id);
+        boundaries.push(start..start + cluster.len());
+    }
+
+    let n = ids.len();
+    let mut suffix_array: Vec<usize> = vec![0; n];
+    let mut left_array: Vec<usize> = vec![0; n];
+    let mut right_array: Vec<usize> = vec![0; n];
+    let mut depth_array: Vec<usize> = vec![0; n];
+    let node_num = if n == 0 {
+        0
+    } else {
+        let alphabet_size = interned.len() as u32;
+        esaxx_rs(
+            &ids,
+            &mut suffix_array,
+            &mut left_array,
+            &mut right_array,
+            &mut depth_array,
+            alphabet_size,
+        )?
+    };
+
+    Ok(GraphemeSuffix {
+        suffix: Suffix {
+            symbols: ids,
+            suffix_array,
+            left_array,
+            right_array,
+            depth_array,
+            node_num,
+        },
+        source: string.to_string(),
+        boundaries,
+    })
+}
+
+/// The suffix array of the extended grapheme clusters of a `String`.
+///
+/// Built by [`suffix_graphemes`]. Unlike [`Suffix`], whose iterator yields
+/// slices of the interned symbol type, this yields `&str` slices of the
+/// original source spanning whole grapheme clusters.
+pub struct GraphemeSuffix {
+    suffix: Suffix<u32, usize>,
+    source: String,
+    boundaries: Vec<Range<usize>>,
+}
+
+impl GraphemeSuffix {
+    pub fn iter(&self) -> GraphemeSuffixIterator<'_> {
+        GraphemeSuffixIterator {
+            i: 0,
+            suffix: self,
+        }
+    }
+}
+
+pub struct GraphemeSuffixIterator<'a> {
+    i: usize,
+    suffix: &'a GraphemeSuffix,
+}
+
+impl<'a> Iterator for GraphemeSuffixIterator<'a> {
+    type Item = (&'a str, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.i;
+        let inner = &self.suffix.suffix;
+        if index == inner.node_num {
+            None
+        } else {
+            let left = inner.left_array[index];
+            let offset = inner.suffix_array[left];
+            let len = inner.depth_array[index];
+            let freq: u32 = (inner.right_array[index] - inner.left_array[index])
+                .try_into()
+                .unwrap();
+            self.i += 1;
+            let slice = if len == 0 {
+                &self.suffix.source[0..0]
+            } else {
+                let start = self.suffix.boundaries[offset].start;
+                let end = self.suffix.boundaries[offset + len - 1].end;
+                &self.suffix.source[start..end]
+            };
+            Some((slice, freq))
+        }
+    }
+}
+
+#[cfg(test)]
+mod grapheme_tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_input() {
+        let suffix = suffix_graphemes("").unwrap();
+        let mut iter = suffix.iter();
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_combining_mark_is_one_cluster() {
+        // "e" + combining acute accent (U+0301) is a single grapheme
+        // cluster ("é"), repeated twice. If combining marks weren't
+        // clustered, the two code points would be counted as separate
+        // symbols and this cluster would never show up as one unit.
+        let string = "e\u{0301}e\u{0301}";
+        let suffix = suffix_graphemes(string).unwrap();
+        let items: Vec<_> = suffix.iter().collect();
+        assert!(
+            items.contains(&("e\u{0301}", 2)),
+            "expected the whole combining-mark cluster to repeat twice, got {:?}",
+            items
+        );
+    }
+
+    #[test]
+    fn test_regional_indicator_flag_is_one_cluster() {
+        // Each flag is a pair of regional-indicator code points that only
+        // forms one grapheme cluster together; splitting on `char` would
+        // instead see two standalone indicator symbols.
+        let flag = "\u{1F1FA}\u{1F1F8}"; // US flag
+        let string = format!("{}{}", flag, flag);
+        let suffix = suffix_graphemes(&string).unwrap();
+        let items: Vec<_> = suffix.iter().collect();
+        assert!(
+            items.contains(&(flag, 2)),
+            "expected the whole flag cluster to repeat twice, got {:?}",
+            items
+        );
+    }
+
+    #[test]
+    fn test_zwj_emoji_sequence_is_one_cluster() {
+        // Man + ZWJ + woman + ZWJ + girl is a single "family" grapheme
+        // cluster, not three independent emoji.
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let string = format!("{}{}", family, family);
+        let suffix = suffix_graphemes(&string).unwrap();
+        let items: Vec<_> = suffix.iter().collect();
+        assert!(
+            items.contains(&(family, 2)),
+            "expected the whole ZWJ sequence to repeat twice, got {:?}",
+            items
+        );
+    }
+}
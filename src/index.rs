@@ -0,0 +1,13 @@
+use std::convert::TryFrom;
+
+/// The integer width used to store suffix array positions.
+///
+/// Implemented for `i32` (the width the C++ FFI in [`crate::c_ver`] uses),
+/// `u32`, and `usize` (the width the safe Rust path has always used).
+/// `esa`/`sais` aren't generic over it yet, so for now it only exists to
+/// let `narrow` shrink an already-built `Suffix` (see its doc comment).
+pub trait Index: Copy + Ord + TryFrom<usize> {}
+
+impl Index for i32 {}
+impl Index for u32 {}
+impl Index for usize {}
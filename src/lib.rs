@@ -38,19 +38,31 @@
 
 pub mod c_ver;
 mod esa;
+mod grapheme;
+mod index;
 mod sais;
 mod structs;
 mod types;
 
 use esa::esaxx_rs;
+use index::Index;
 use structs::Suffix;
 use types::SuffixError;
 
+pub use grapheme::{suffix_graphemes, GraphemeSuffix, GraphemeSuffixIterator};
+pub use structs::{AnyLocate, AnySuffix, AnySuffixIterator};
+
 /// Creates the suffix array and provides an iterator over its items (Rust version)
 /// See [suffix](fn.suffix.html)
 ///
 /// Gives you an iterator over the suffixes of the input array and their count within
 /// the input string.
+///
+/// Narrows the result down to `u32` positions for anything under
+/// `u32::MAX` chars, falling back to `usize` for huge inputs — see
+/// `narrow`'s doc comment below for what that narrowing does and doesn't
+/// buy you. Either way [`AnySuffix::iter`] yields the same `(&[char], u32)`
+/// items.
 /// ```rust
 /// let string = "abracadabra";
 /// let suffix = esaxx_rs::suffix_rs(string).unwrap();
@@ -63,25 +75,87 @@ use types::SuffixError;
 /// assert_eq!(iter.next(), Some((&chars[..0], 11))); // ''
 /// assert_eq!(iter.next(), None);
 /// ```
-pub fn suffix_rs(string: &str) -> Result<Suffix<usize>, SuffixError> {
+pub fn suffix_rs(string: &str) -> Result<AnySuffix<char>, SuffixError> {
     let chars: Vec<_> = string.chars().collect();
-    let n: usize = chars.len();
-    let u32_chars: Vec<u32> = chars.iter().map(|c| *c as u32).collect::<Vec<_>>();
+    let wide = suffix_slice(&chars, 0x110000)?; // All UCS4 range.
+    if wide.suffix_array.len() < u32::MAX as usize {
+        Ok(AnySuffix::U32(narrow(wide)))
+    } else {
+        Ok(AnySuffix::Usize(wide))
+    }
+}
+
+/// Narrows every array of a `usize`-indexed [`Suffix`] down to the given
+/// [`Index`] width, after the fact. `esa`/`sais` only ever compute at
+/// `usize` width, so this is a post-hoc `O(n)` copy into smaller storage,
+/// not a cheaper construction — it trades one extra allocation+copy for a
+/// smaller long-lived [`Suffix`].
+fn narrow<S, Idx: Index>(suffix: Suffix<S, usize>) -> Suffix<S, Idx> {
+    let to_idx = |values: Vec<usize>| -> Vec<Idx> {
+        values
+            .into_iter()
+            .map(|v| Idx::try_from(v).ok().unwrap())
+            .collect()
+    };
+    Suffix {
+        symbols: suffix.symbols,
+        suffix_array: to_idx(suffix.suffix_array),
+        left_array: to_idx(suffix.left_array),
+        right_array: to_idx(suffix.right_array),
+        depth_array: to_idx(suffix.depth_array),
+        node_num: suffix.node_num,
+    }
+}
+
+/// Creates the suffix array over a sequence of integer token ids (e.g. the
+/// piece ids produced by a BPE/unigram tokenizer), without going through a
+/// `char` representation at all.
+///
+/// This is the entry point SentencePiece-style callers want: they already
+/// have `&[u32]` token ids and `alphabet_size = vocab_size`, and routing
+/// them through UTF-8 text would be both lossy and wasteful.
+/// ```rust
+/// // token ids for "abracadabra" over a dense 5-piece alphabet
+/// // (a=0, b=1, c=2, d=3, r=4)
+/// let tokens = vec![0u32, 1, 4, 0, 2, 0, 3, 0, 1, 4, 0];
+/// let suffix = esaxx_rs::suffix_from_ids(&tokens, 5).unwrap();
+/// let mut iter = suffix.iter();
+/// assert_eq!(iter.next(), Some((&tokens[..4], 2))); // [0, 1, 4, 0] ("abra")
+/// ```
+pub fn suffix_from_ids(tokens: &[u32], alphabet_size: u32) -> Result<Suffix<u32, usize>, SuffixError> {
+    suffix_slice(tokens, alphabet_size)
+}
+
+/// Creates the suffix array over any sequence of symbols that can be widened
+/// into a `u32` (the alphabet `esaxx_rs` buckets symbols by), keeping the
+/// caller's original symbol type `S` in the returned [`Suffix`] so its
+/// iterator yields slices of the caller's own tokens rather than `char`s.
+///
+/// [`suffix_rs`] and [`suffix_from_ids`] are thin wrappers around this.
+pub fn suffix_slice<S: Into<u32> + Copy>(
+    symbols: &[S],
+    alphabet_size: u32,
+) -> Result<Suffix<S, usize>, SuffixError> {
+    let n: usize = symbols.len();
+    let u32_symbols: Vec<u32> = symbols.iter().map(|s| (*s).into()).collect::<Vec<_>>();
     let mut suffix_array: Vec<usize> = vec![0; n];
     let mut left_array: Vec<usize> = vec![0; n];
     let mut right_array: Vec<usize> = vec![0; n];
     let mut depth_array: Vec<usize> = vec![0; n];
-    let alphabet_size = 0x110000; // All UCS4 range.
-    let node_num = esaxx_rs(
-        &u32_chars,
-        &mut suffix_array,
-        &mut left_array,
-        &mut right_array,
-        &mut depth_array,
-        alphabet_size,
-    )?;
+    let node_num = if n == 0 {
+        0
+    } else {
+        esaxx_rs(
+            &u32_symbols,
+            &mut suffix_array,
+            &mut left_array,
+            &mut right_array,
+            &mut depth_array,
+            alphabet_size,
+        )?
+    };
     Ok(Suffix {
-        chars,
+        symbols: symbols.to_vec(),
         suffix_array,
         left_array,
         right_array,
@@ -140,7 +214,10 @@ mod rs_tests {
 
     #[test]
     fn test_suffix_rs() {
-        let suffix = suffix_rs("abracadabra").unwrap();
+        let suffix = match suffix_rs("abracadabra").unwrap() {
+            AnySuffix::U32(suffix) => suffix,
+            AnySuffix::Usize(_) => panic!("expected the narrow u32 width for a short input"),
+        };
         assert_eq!(suffix.node_num, 5);
         assert_eq!(suffix.suffix_array, vec![10, 7, 0, 3, 5, 8, 1, 4, 6, 9, 2]);
         assert_eq!(suffix.left_array, vec![1, 0, 5, 9, 0, 0, 3, 0, 0, 0, 2]);
@@ -157,9 +234,94 @@ mod rs_tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn test_suffix_from_ids() {
+        // Same relative symbol order as "abracadabra" (a < b < c < d < r),
+        // just relabeled onto a dense 5-piece alphabet instead of chars, so
+        // the expected arrays are the same ones `test_suffix_rs` verifies.
+        let tokens: Vec<u32> = vec![0, 1, 4, 0, 2, 0, 3, 0, 1, 4, 0];
+        let suffix = suffix_from_ids(&tokens, 5).unwrap();
+        assert_eq!(suffix.node_num, 5);
+        assert_eq!(suffix.suffix_array, vec![10, 7, 0, 3, 5, 8, 1, 4, 6, 9, 2]);
+        assert_eq!(suffix.left_array, vec![1, 0, 5, 9, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(suffix.right_array, vec![3, 5, 7, 11, 11, 1, 0, 1, 0, 0, 0]);
+        assert_eq!(suffix.depth_array, vec![4, 1, 3, 2, 0, 0, 0, 0, 0, 0, 0]);
+
+        let mut iter = suffix.iter();
+        assert_eq!(iter.next(), Some((&tokens[..4], 2))); // [0, 1, 4, 0] ("abra")
+        assert_eq!(iter.next(), Some((&tokens[..1], 5))); // [0] ("a")
+        assert_eq!(iter.next(), Some((&tokens[1..4], 2))); // [1, 4, 0] ("bra")
+        assert_eq!(iter.next(), Some((&tokens[2..4], 2))); // [4, 0] ("ra")
+        assert_eq!(iter.next(), Some((&tokens[..0], 11))); // [] ("")
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_suffix_rs_empty() {
+        let suffix = suffix_rs("").unwrap();
+        assert_eq!(suffix.iter().next(), None);
+    }
+
+    #[test]
+    fn test_suffix_from_ids_empty() {
+        let suffix = suffix_from_ids(&[], 0).unwrap();
+        assert_eq!(suffix.iter().next(), None);
+    }
+
     #[test]
     fn test_out_of_bounds_bug() {
         let string = "banana$band$$";
         suffix_rs(string).unwrap();
     }
+
+    #[test]
+    fn test_count_and_locate() {
+        let suffix = match suffix_rs("abracadabra").unwrap() {
+            AnySuffix::U32(suffix) => suffix,
+            AnySuffix::Usize(_) => panic!("expected the narrow u32 width for a short input"),
+        };
+        let pattern: Vec<_> = "abra".chars().collect();
+        assert_eq!(suffix.count(&pattern), 2);
+        assert_eq!(suffix.locate(&pattern), &[7u32, 0]);
+
+        let pattern: Vec<_> = "a".chars().collect();
+        assert_eq!(suffix.count(&pattern), 5);
+
+        let pattern: Vec<_> = "xyz".chars().collect();
+        assert_eq!(suffix.count(&pattern), 0);
+        assert_eq!(suffix.locate(&pattern), &[] as &[u32]);
+
+        assert_eq!(suffix.count(&[]), 11);
+    }
+
+    #[test]
+    fn test_any_suffix_passthrough() {
+        // count/locate/lcp_array must be reachable directly on AnySuffix,
+        // without the caller matching out the U32/Usize variant first.
+        let suffix = suffix_rs("abracadabra").unwrap();
+        let pattern: Vec<_> = "abra".chars().collect();
+        assert_eq!(suffix.count(&pattern), 2);
+        match suffix.locate(&pattern) {
+            AnyLocate::U32(positions) => assert_eq!(positions, &[7u32, 0]),
+            AnyLocate::Usize(_) => panic!("expected the narrow u32 width for a short input"),
+        }
+        assert_eq!(
+            suffix.lcp_array(),
+            vec![0, 1, 4, 1, 1, 0, 3, 0, 0, 0, 2]
+        );
+    }
+
+    #[test]
+    fn test_lcp_array() {
+        let suffix = match suffix_rs("abracadabra").unwrap() {
+            AnySuffix::U32(suffix) => suffix,
+            AnySuffix::Usize(_) => panic!("expected the narrow u32 width for a short input"),
+        };
+        // Suffixes in SA order: "a", "abra", "abracadabra", "acadabra",
+        // "adabra", "bra", "bracadabra", "cadabra", "dabra", "ra", "racadabra"
+        assert_eq!(
+            suffix.lcp_array(),
+            vec![0, 1, 4, 1, 1, 0, 3, 0, 0, 0, 2]
+        );
+    }
 }
@@ -1,12 +1,20 @@
+use std::cmp::Ordering;
 use std::convert::TryInto;
 
-pub struct SuffixIterator<'a, T> {
+pub struct SuffixIterator<'a, S, T> {
     pub(super) i: usize,
-    pub(super) suffix: &'a Suffix<T>,
+    pub(super) suffix: &'a Suffix<S, T>,
 }
 
-pub struct Suffix<T> {
-    pub(super) chars: Vec<char>,
+/// The suffix array of a sequence of symbols of type `S`, indexed with `T`.
+///
+/// `S` is the alphabet the caller built the sequence from (`char` for
+/// [`crate::suffix_rs`], `u32` for [`crate::suffix_from_ids`]). `T` is the
+/// integer width used to store positions (`usize` as built by the safe Rust
+/// path, `u32` once [`crate::suffix_rs`] narrows it, `i32` for the
+/// [`crate::c_ver`] FFI path).
+pub struct Suffix<S, T> {
+    pub(super) symbols: Vec<S>,
     pub(super) suffix_array: Vec<T>,
     pub(super) left_array: Vec<T>,
     pub(super) right_array: Vec<T>,
@@ -14,14 +22,174 @@ pub struct Suffix<T> {
     pub(super) node_num: usize,
 }
 
-impl<T> Suffix<T> {
-    pub fn iter(&self) -> SuffixIterator<'_, T> {
+impl<S, T> Suffix<S, T> {
+    pub fn iter(&self) -> SuffixIterator<'_, S, T> {
         SuffixIterator { i: 0, suffix: self }
     }
 }
 
-impl<'a> Iterator for SuffixIterator<'a, i32> {
-    type Item = (&'a [char], u32);
+impl<S: Ord, T: Copy + TryInto<usize>> Suffix<S, T> {
+    /// Compares `pattern` against the suffix starting at `offset`, treating
+    /// a suffix that starts with (or equals) `pattern` as equal; a suffix
+    /// that is a proper prefix of `pattern` compares as less.
+    /// This is what lets `lower_bound`/`upper_bound` carve out the rank
+    /// interval of suffixes that have `pattern` as a prefix.
+    fn prefix_cmp(&self, offset: usize, pattern: &[S]) -> Ordering {
+        let suffix_len = self.symbols.len() - offset;
+        for (a, b) in self.symbols[offset..].iter().zip(pattern.iter()) {
+            match a.cmp(b) {
+                Ordering::Equal => continue,
+                other => return other,
+            }
+        }
+        if suffix_len < pattern.len() {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// First rank whose suffix is not lexicographically before `pattern`.
+    fn lower_bound(&self, pattern: &[S]) -> usize {
+        let (mut lo, mut hi) = (0, self.suffix_array.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let offset = self.suffix_array[mid].try_into().ok().unwrap();
+            if self.prefix_cmp(offset, pattern) == Ordering::Less {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// First rank whose suffix is after `pattern` and does not start with it.
+    fn upper_bound(&self, pattern: &[S]) -> usize {
+        let (mut lo, mut hi) = (0, self.suffix_array.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let offset = self.suffix_array[mid].try_into().ok().unwrap();
+            if self.prefix_cmp(offset, pattern) == Ordering::Greater {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        lo
+    }
+
+    /// Number of suffixes that have `pattern` as a prefix.
+    pub fn count(&self, pattern: &[S]) -> usize {
+        self.upper_bound(pattern) - self.lower_bound(pattern)
+    }
+
+    /// Starting positions of the suffixes that have `pattern` as a prefix.
+    pub fn locate(&self, pattern: &[S]) -> &[T] {
+        &self.suffix_array[self.lower_bound(pattern)..self.upper_bound(pattern)]
+    }
+}
+
+/// Either index width [`crate::suffix_rs`] may hand back, depending on
+/// whether the input was small enough to fit the narrower `u32` positions.
+pub enum AnySuffix<S> {
+    U32(Suffix<S, u32>),
+    Usize(Suffix<S, usize>),
+}
+
+impl<S> AnySuffix<S> {
+    pub fn iter(&self) -> AnySuffixIterator<'_, S> {
+        match self {
+            AnySuffix::U32(suffix) => AnySuffixIterator::U32(suffix.iter()),
+            AnySuffix::Usize(suffix) => AnySuffixIterator::Usize(suffix.iter()),
+        }
+    }
+}
+
+impl<S: Ord> AnySuffix<S> {
+    /// Number of suffixes that have `pattern` as a prefix. See [`Suffix::count`].
+    pub fn count(&self, pattern: &[S]) -> usize {
+        match self {
+            AnySuffix::U32(suffix) => suffix.count(pattern),
+            AnySuffix::Usize(suffix) => suffix.count(pattern),
+        }
+    }
+
+    /// Starting positions of the suffixes that have `pattern` as a prefix.
+    /// See [`Suffix::locate`].
+    pub fn locate(&self, pattern: &[S]) -> AnyLocate<'_> {
+        match self {
+            AnySuffix::U32(suffix) => AnyLocate::U32(suffix.locate(pattern)),
+            AnySuffix::Usize(suffix) => AnyLocate::Usize(suffix.locate(pattern)),
+        }
+    }
+}
+
+impl<S: PartialEq> AnySuffix<S> {
+    /// The longest-common-prefix array of the suffix array. See [`Suffix::lcp_array`].
+    pub fn lcp_array(&self) -> Vec<usize> {
+        match self {
+            AnySuffix::U32(suffix) => suffix.lcp_array(),
+            AnySuffix::Usize(suffix) => suffix.lcp_array(),
+        }
+    }
+}
+
+pub enum AnySuffixIterator<'a, S> {
+    U32(SuffixIterator<'a, S, u32>),
+    Usize(SuffixIterator<'a, S, usize>),
+}
+
+impl<'a, S> Iterator for AnySuffixIterator<'a, S> {
+    type Item = (&'a [S], u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            AnySuffixIterator::U32(iter) => iter.next(),
+            AnySuffixIterator::Usize(iter) => iter.next(),
+        }
+    }
+}
+
+/// Either index width [`AnySuffix::locate`] may hand back, matching
+/// whichever variant the [`AnySuffix`] it was called on holds.
+pub enum AnyLocate<'a> {
+    U32(&'a [u32]),
+    Usize(&'a [usize]),
+}
+
+impl<S: PartialEq, T: Copy + TryInto<usize>> Suffix<S, T> {
+    /// The longest-common-prefix array of the suffix array, computed with
+    /// Kasai's algorithm: `lcp[rank[i]]` is the length of the common prefix
+    /// shared between the suffix starting at `i` and the suffix immediately
+    /// before it in suffix-array order.
+    pub fn lcp_array(&self) -> Vec<usize> {
+        let n = self.symbols.len();
+        let mut rank = vec![0usize; n];
+        for (i, &sa_i) in self.suffix_array.iter().enumerate() {
+            rank[sa_i.try_into().ok().unwrap()] = i;
+        }
+
+        let mut lcp = vec![0usize; n];
+        let mut h = 0usize;
+        for i in 0..n {
+            if rank[i] > 0 {
+                let j: usize = self.suffix_array[rank[i] - 1].try_into().ok().unwrap();
+                while i + h < n && j + h < n && self.symbols[i + h] == self.symbols[j + h] {
+                    h += 1;
+                }
+                lcp[rank[i]] = h;
+                h = h.saturating_sub(1);
+            } else {
+                h = 0;
+            }
+        }
+        lcp
+    }
+}
+
+impl<'a, S> Iterator for SuffixIterator<'a, S, i32> {
+    type Item = (&'a [S], u32);
 
     fn next(&mut self) -> Option<Self::Item> {
         let index = self.i;
@@ -35,13 +203,13 @@ impl<'a> Iterator for SuffixIterator<'a, i32> {
                 .try_into()
                 .ok()?;
             self.i += 1;
-            Some((&self.suffix.chars[offset..offset + len], freq))
+            Some((&self.suffix.symbols[offset..offset + len], freq))
         }
     }
 }
 
-impl<'a> Iterator for SuffixIterator<'a, usize> {
-    type Item = (&'a [char], u32);
+impl<'a, S> Iterator for SuffixIterator<'a, S, usize> {
+    type Item = (&'a [S], u32);
 
     fn next(&mut self) -> Option<Self::Item> {
         let index = self.i;
@@ -55,7 +223,25 @@ impl<'a> Iterator for SuffixIterator<'a, usize> {
                 .try_into()
                 .unwrap();
             self.i += 1;
-            Some((&self.suffix.chars[offset..offset + len], freq))
+            Some((&self.suffix.symbols[offset..offset + len], freq))
+        }
+    }
+}
+
+impl<'a, S> Iterator for SuffixIterator<'a, S, u32> {
+    type Item = (&'a [S], u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.i;
+        if index == self.suffix.node_num {
+            None
+        } else {
+            let left: usize = self.suffix.left_array[index].try_into().ok()?;
+            let offset: usize = self.suffix.suffix_array[left].try_into().ok()?;
+            let len: usize = self.suffix.depth_array[index].try_into().ok()?;
+            let freq: u32 = self.suffix.right_array[index] - self.suffix.left_array[index];
+            self.i += 1;
+            Some((&self.suffix.symbols[offset..offset + len], freq))
         }
     }
 }